@@ -0,0 +1,386 @@
+use crate::{LongColumnWidths, LongRow};
+use colored::Color;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Extensions recognized as tar archives, with or without gzip compression.
+const ARCHIVE_SUFFIXES: &[&str] = &[".tar", ".tgz", ".tar.gz"];
+
+pub fn is_archive(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let lower = name.to_lowercase();
+    ARCHIVE_SUFFIXES.iter().any(|suffix| lower.ends_with(suffix))
+}
+
+fn is_gzip(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let lower = name.to_lowercase();
+    lower.ends_with(".tgz") || lower.ends_with(".tar.gz")
+}
+
+/// One member of a tar archive, carrying just enough of its header to be
+/// rendered through the real long/short formatters — name, size, mode,
+/// mtime, ownership, and (for symlinks) the link target. Unlike `FileInfo`
+/// there's no backing `fs::Metadata`: that can't be synthesized without a
+/// real file, so archive entries get their own small formatting path in
+/// this module instead of flowing through `FileInfo` itself.
+pub struct Entry {
+    name: String,
+    size: u64,
+    mode: u32,
+    mtime: i64,
+    uid: u32,
+    gid: u32,
+    is_dir: bool,
+    is_symlink: bool,
+    link_target: Option<String>,
+}
+
+/// An archive's members, grouped by virtual parent directory (`""` for the
+/// archive root) so `-R` can walk the archive's own directory structure the
+/// same way it walks a real one.
+pub struct Tree {
+    by_parent: HashMap<String, Vec<Entry>>,
+}
+
+impl Tree {
+    fn children(&self, dir: &str) -> &[Entry] {
+        self.by_parent.get(dir).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// Streams `path`'s tar headers into a [`Tree`], never reading member
+/// content: the `tar` crate's entry iterator skips each entry's unread data
+/// when advancing to the next header, so this never buffers a member's
+/// bytes or writes anything to disk. Entries that imply directories tar
+/// never wrote a header for (common for plain-old tarballs, which only
+/// record leaf paths) get a synthetic directory entry so `-R` still finds
+/// them. Returns `None` for archives we can't open or parse (unsupported
+/// compression, corruption) so the caller can fall back to normal behavior.
+pub fn scan(path: &Path) -> Option<Tree> {
+    let file = File::open(path).ok()?;
+    let reader: Box<dyn Read> = if is_gzip(path) {
+        Box::new(flate2::read::GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+
+    let mut archive = tar::Archive::new(reader);
+    let mut by_parent: HashMap<String, Vec<Entry>> = HashMap::new();
+    let mut known_dirs: HashSet<String> = HashSet::new();
+    known_dirs.insert(String::new());
+
+    for raw_entry in archive.entries().ok()? {
+        let entry = raw_entry.ok()?;
+        let header = entry.header();
+        let is_dir = header.entry_type().is_dir();
+        let is_symlink = header.entry_type().is_symlink();
+
+        let raw_path = entry.path().ok()?;
+        let full = normalize(&raw_path.display().to_string());
+        if full.is_empty() {
+            continue;
+        }
+
+        let link_target = entry
+            .link_name()
+            .ok()
+            .flatten()
+            .map(|p| p.display().to_string());
+
+        let (parent, name) = split_parent(&full);
+        ensure_ancestors(parent, &mut known_dirs, &mut by_parent);
+
+        if is_dir && !known_dirs.insert(full.clone()) {
+            // A synthetic ancestor (or duplicate header) already claimed
+            // this path; the real header is authoritative, so patch its
+            // fields in place rather than discarding them.
+            if let Some(existing) = by_parent
+                .get_mut(parent)
+                .and_then(|siblings| siblings.iter_mut().find(|e| e.name == name))
+            {
+                existing.size = header.size().unwrap_or(existing.size);
+                existing.mode = header.mode().unwrap_or(existing.mode);
+                existing.mtime = header.mtime().map(|t| t as i64).unwrap_or(existing.mtime);
+                existing.uid = header.uid().map(|u| u as u32).unwrap_or(existing.uid);
+                existing.gid = header.gid().map(|g| g as u32).unwrap_or(existing.gid);
+            }
+            continue;
+        }
+
+        by_parent.entry(parent.to_string()).or_default().push(Entry {
+            name: name.to_string(),
+            size: header.size().unwrap_or(0),
+            mode: header.mode().unwrap_or(0o644),
+            mtime: header.mtime().unwrap_or(0) as i64,
+            uid: header.uid().unwrap_or(0) as u32,
+            gid: header.gid().unwrap_or(0) as u32,
+            is_dir,
+            is_symlink,
+            link_target,
+        });
+    }
+
+    Some(Tree { by_parent })
+}
+
+/// Strips a leading `./` and any trailing `/` so archive paths compare
+/// consistently regardless of how the archiver wrote them.
+fn normalize(raw: &str) -> String {
+    raw.trim_start_matches("./").trim_end_matches('/').to_string()
+}
+
+fn split_parent(full: &str) -> (&str, &str) {
+    match full.rsplit_once('/') {
+        Some((parent, name)) => (parent, name),
+        None => ("", full),
+    }
+}
+
+/// Makes sure every ancestor directory implied by `parent` (e.g. `a/b` also
+/// implies `a`) has a synthetic entry under *its* parent, so a tarball that
+/// only records leaf paths still produces a walkable directory structure.
+fn ensure_ancestors(
+    parent: &str,
+    known_dirs: &mut HashSet<String>,
+    by_parent: &mut HashMap<String, Vec<Entry>>,
+) {
+    if parent.is_empty() || known_dirs.contains(parent) {
+        return;
+    }
+
+    let (grandparent, name) = split_parent(parent);
+    ensure_ancestors(grandparent, known_dirs, by_parent);
+
+    known_dirs.insert(parent.to_string());
+    by_parent.entry(grandparent.to_string()).or_default().push(Entry {
+        name: name.to_string(),
+        size: 0,
+        mode: 0o755,
+        mtime: 0,
+        uid: 0,
+        gid: 0,
+        is_dir: true,
+        is_symlink: false,
+        link_target: None,
+    });
+}
+
+/// Renders `dir`'s entries through the same long/short layouts a real
+/// directory uses, sorted per `opt`'s sort flags. Returns the virtual paths
+/// of the subdirectories found, for the caller to recurse into under `-R`.
+pub fn render(tree: &Tree, dir: &str, opt: &crate::Opt) -> Vec<String> {
+    let use_color = crate::should_use_color(&opt.color);
+    let show_counts = !opt.no_dir_counts;
+
+    let mut entries: Vec<&Entry> = tree
+        .children(dir)
+        .iter()
+        .filter(|e| crate::should_show_file(&e.name, opt.all))
+        .collect();
+    sort_entries(&mut entries, opt);
+
+    let (directories, files): (Vec<&Entry>, Vec<&Entry>) =
+        entries.into_iter().partition(|e| e.is_dir);
+
+    if opt.long {
+        let build = |e: &&Entry| build_row(e, dir, tree, use_color, show_counts, opt);
+
+        let directory_rows: Vec<LongRow> = directories.iter().map(build).collect();
+        let file_rows: Vec<LongRow> = files.iter().map(build).collect();
+
+        let all_rows: Vec<&LongRow> = directory_rows.iter().chain(file_rows.iter()).collect();
+        let widths = LongColumnWidths {
+            nlink: all_rows.iter().map(|r| r.nlink.len()).max().unwrap_or(0),
+            owner: all_rows.iter().map(|r| r.owner.len()).max().unwrap_or(0),
+            group: all_rows.iter().map(|r| r.group.len()).max().unwrap_or(0),
+            size: all_rows.iter().map(|r| r.size.len()).max().unwrap_or(0),
+            inode: all_rows.iter().map(|r| r.inode.len()).max().unwrap_or(0),
+            blocks: all_rows.iter().map(|r| r.blocks.len()).max().unwrap_or(0),
+        };
+
+        crate::print_long_rows(&directory_rows, opt.inode, opt.blocks, &widths);
+        if !directory_rows.is_empty() && !file_rows.is_empty() {
+            println!();
+        }
+        crate::print_long_rows(&file_rows, opt.inode, opt.blocks, &widths);
+    } else {
+        let term_width = crate::grid::terminal_width();
+        println!();
+
+        if !directories.is_empty() {
+            let names: Vec<String> = directories
+                .iter()
+                .map(|e| format_name(e, dir, tree, use_color, show_counts, opt.classify, false))
+                .collect();
+            print!("{}", crate::grid::render(&names, term_width, opt.across, opt.oneline));
+        }
+        if !files.is_empty() {
+            let names: Vec<String> = files
+                .iter()
+                .map(|e| format_name(e, dir, tree, use_color, show_counts, opt.classify, false))
+                .collect();
+            print!("{}", crate::grid::render(&names, term_width, opt.across, opt.oneline));
+        }
+        println!();
+    }
+
+    directories
+        .iter()
+        .map(|e| {
+            if dir.is_empty() {
+                e.name.clone()
+            } else {
+                format!("{dir}/{}", e.name)
+            }
+        })
+        .collect()
+}
+
+fn sort_entries(entries: &mut [&Entry], opt: &crate::Opt) {
+    match opt.sort_key() {
+        crate::SortKey::Unsorted => {}
+        crate::SortKey::Size => entries.sort_by_key(|e| std::cmp::Reverse(e.size)),
+        crate::SortKey::Extension => entries.sort_by(|a, b| {
+            let ext_a = Path::new(&a.name).extension().and_then(|s| s.to_str()).unwrap_or("");
+            let ext_b = Path::new(&b.name).extension().and_then(|s| s.to_str()).unwrap_or("");
+            ext_a.cmp(ext_b).then_with(|| a.name.cmp(&b.name))
+        }),
+        crate::SortKey::Natural => entries.sort_by(|a, b| crate::natural::natural_cmp(&a.name, &b.name)),
+        crate::SortKey::Time => entries.sort_by_key(|e| e.mtime),
+        crate::SortKey::Name => entries.sort_by(|a, b| a.name.cmp(&b.name)),
+    }
+
+    if opt.reverse {
+        entries.reverse();
+    }
+}
+
+fn build_row(
+    entry: &Entry,
+    dir: &str,
+    tree: &Tree,
+    use_color: bool,
+    show_counts: bool,
+    opt: &crate::Opt,
+) -> LongRow {
+    let type_bit = if entry.is_dir {
+        libc::S_IFDIR
+    } else if entry.is_symlink {
+        libc::S_IFLNK
+    } else {
+        libc::S_IFREG
+    };
+
+    let time = chrono::DateTime::from_timestamp(entry.mtime, 0)
+        .map(|dt| dt.with_timezone(&chrono::Local).format("%b %d %H:%M").to_string())
+        .unwrap_or_else(|| "???".to_string());
+
+    LongRow {
+        permissions: crate::format_permissions(entry.mode | type_bit),
+        nlink: "1".to_string(),
+        owner: crate::resolve_user_name(entry.uid),
+        group: crate::resolve_group_name(entry.gid),
+        size: crate::format_size(entry.size, opt.human_readable),
+        inode: "0".to_string(),
+        blocks: "0".to_string(),
+        time,
+        git_column: None,
+        name: format_name(entry, dir, tree, use_color, show_counts, opt.classify, true),
+    }
+}
+
+fn format_name(
+    entry: &Entry,
+    dir: &str,
+    tree: &Tree,
+    use_color: bool,
+    show_counts: bool,
+    classify: bool,
+    long: bool,
+) -> String {
+    let colored = colorize_name(entry, use_color);
+
+    let mut name = if entry.is_dir {
+        if show_counts {
+            let full_path = if dir.is_empty() {
+                entry.name.clone()
+            } else {
+                format!("{dir}/{}", entry.name)
+            };
+            format!("{colored}({})", tree.children(&full_path).len())
+        } else {
+            format!("{colored}/")
+        }
+    } else if classify {
+        format!("{colored}{}", classify_indicator(entry))
+    } else {
+        colored
+    };
+
+    // Like real files, the `-> target` arrow only appears in long format.
+    if long && entry.is_symlink {
+        if let Some(target) = &entry.link_target {
+            name = format!("{name} -> {target}");
+        }
+    }
+
+    name
+}
+
+fn colorize_name(entry: &Entry, use_color: bool) -> String {
+    if !use_color {
+        return entry.name.clone();
+    }
+
+    match style_for(entry) {
+        Some(style) => style.paint(&entry.name),
+        None => entry.name.clone(),
+    }
+}
+
+/// Mirrors `get_file_color`'s precedence (type, then executable bit, then
+/// extension) using an archive entry's header fields in place of real
+/// `fs::Metadata`.
+fn style_for(entry: &Entry) -> Option<crate::ls_colors::Style> {
+    let ls_colors = crate::ls_colors::parsed();
+    let named = crate::ls_colors::Style::Named;
+
+    if entry.is_dir {
+        return Some(ls_colors.type_style("di").unwrap_or(named(Color::BrightCyan)));
+    }
+    if entry.is_symlink {
+        return Some(ls_colors.type_style("ln").unwrap_or(named(Color::Red)));
+    }
+    if entry.mode & (libc::S_IXUSR | libc::S_IXGRP | libc::S_IXOTH) != 0 {
+        return Some(ls_colors.type_style("ex").unwrap_or(named(Color::BrightGreen)));
+    }
+    if let Some(style) = ls_colors.style_for_filename(&entry.name) {
+        return Some(style);
+    }
+
+    match Path::new(&entry.name).extension().and_then(|s| s.to_str()) {
+        Some(extension) => Some(named(crate::builtin_extension_color(extension))),
+        None => ls_colors.type_style("fi"),
+    }
+}
+
+/// `-F`/`--classify` suffix for non-directory entries, mirroring
+/// `classify_indicator` for real files (archives don't carry FIFOs or
+/// sockets, so those cases don't apply here).
+fn classify_indicator(entry: &Entry) -> &'static str {
+    if entry.is_symlink {
+        return "@";
+    }
+    if entry.mode & (libc::S_IXUSR | libc::S_IXGRP | libc::S_IXOTH) != 0 {
+        "*"
+    } else {
+        ""
+    }
+}
@@ -0,0 +1,123 @@
+use colored::Colorize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Per-file git status: one char for the index (staged) state and one for
+/// the working-tree (unstaged) state, e.g. `M-`, `-?`, `--`.
+pub struct GitStatus {
+    staged: char,
+    unstaged: char,
+}
+
+type StatusMap = Arc<HashMap<PathBuf, GitStatus>>;
+
+/// Discovers the git repository containing `dir` (if any) and returns its
+/// status map, keyed by each entry's canonical path. The map is computed
+/// once per repository (keyed by the repo's workdir) and cached for the
+/// life of the process, so an `-R` walk that revisits the same repository
+/// across many subdirectories only queries git once. Returns `None` when
+/// `dir` isn't inside a git working tree.
+pub fn load(dir: &Path) -> Option<StatusMap> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, Option<StatusMap>>>> = OnceLock::new();
+
+    let repo = git2::Repository::discover(dir).ok()?;
+    let workdir = repo.workdir()?.to_path_buf();
+
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cache = cache.lock().unwrap();
+    if let Some(cached) = cache.get(&workdir) {
+        return cached.clone();
+    }
+
+    let map = load_statuses(&repo, &workdir).map(Arc::new);
+    cache.insert(workdir, map.clone());
+    map
+}
+
+fn load_statuses(repo: &git2::Repository, workdir: &Path) -> Option<HashMap<PathBuf, GitStatus>> {
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+    let statuses = repo.statuses(Some(&mut opts)).ok()?;
+
+    let mut map = HashMap::new();
+    for entry in statuses.iter() {
+        let Some(rel_path) = entry.path() else {
+            continue;
+        };
+        let full_path = workdir.join(rel_path);
+        let canonical = fs::canonicalize(&full_path).unwrap_or(full_path);
+        let status = entry.status();
+
+        map.insert(
+            canonical,
+            GitStatus {
+                staged: staged_char(status),
+                unstaged: unstaged_char(status),
+            },
+        );
+    }
+
+    Some(map)
+}
+
+fn staged_char(status: git2::Status) -> char {
+    if status.is_index_new() {
+        'A'
+    } else if status.is_index_modified() {
+        'M'
+    } else if status.is_index_deleted() {
+        'D'
+    } else if status.is_index_renamed() {
+        'R'
+    } else if status.is_index_typechange() {
+        'T'
+    } else {
+        '-'
+    }
+}
+
+fn unstaged_char(status: git2::Status) -> char {
+    if status.is_wt_new() {
+        '?'
+    } else if status.is_wt_modified() {
+        'M'
+    } else if status.is_wt_deleted() {
+        'D'
+    } else if status.is_wt_renamed() {
+        'R'
+    } else if status.is_wt_typechange() {
+        'T'
+    } else {
+        '-'
+    }
+}
+
+/// Renders the two-character git status column for `path`, coloring the
+/// staged half green and the unstaged half red; files outside the repo (or
+/// with no status entry) show as unmodified (`--`).
+pub fn column(statuses: &HashMap<PathBuf, GitStatus>, path: &Path, use_color: bool) -> String {
+    let canonical = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let status = statuses.get(&canonical);
+
+    let staged = status.map_or('-', |s| s.staged);
+    let unstaged = status.map_or('-', |s| s.unstaged);
+
+    if !use_color {
+        return format!("{}{}", staged, unstaged);
+    }
+
+    let staged_str = if staged == '-' {
+        staged.to_string()
+    } else {
+        staged.to_string().green().to_string()
+    };
+    let unstaged_str = if unstaged == '-' {
+        unstaged.to_string()
+    } else {
+        unstaged.to_string().red().to_string()
+    };
+
+    format!("{}{}", staged_str, unstaged_str)
+}
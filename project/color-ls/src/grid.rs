@@ -0,0 +1,116 @@
+use std::cmp;
+use terminal_size::{terminal_size, Width};
+
+const COLUMN_SPACING: usize = 2;
+
+/// Terminal width to wrap grid output to, falling back to 80 columns when
+/// stdout isn't a TTY (e.g. piped output).
+pub fn terminal_width() -> usize {
+    terminal_size()
+        .map(|(Width(w), _)| w as usize)
+        .unwrap_or(80)
+}
+
+/// Visible width of `s`, ignoring ANSI SGR escape sequences (`\x1b[...m`) so
+/// colored entries still line up with plain ones.
+pub fn visible_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' && chars.peek() == Some(&'[') {
+            chars.next();
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+            continue;
+        }
+        width += 1;
+    }
+
+    width
+}
+
+/// Lay `entries` out in the widest grid that fits `term_width`: try the
+/// largest column count first and shrink until every column's max width
+/// (plus inter-column padding) fits.
+///
+/// When `across` is true entries fill row-major, left to right, like
+/// `ls -x`; otherwise they fill column-major, top to bottom, like plain
+/// `ls`. When `oneline` is true, the grid is skipped entirely and each
+/// entry gets its own line, like `ls -1`.
+pub fn render(entries: &[String], term_width: usize, across: bool, oneline: bool) -> String {
+    if entries.is_empty() {
+        return String::new();
+    }
+
+    if oneline {
+        let mut out = String::new();
+        for entry in entries {
+            out.push_str(entry);
+            out.push('\n');
+        }
+        return out;
+    }
+
+    let widths: Vec<usize> = entries.iter().map(|e| visible_width(e)).collect();
+    let (columns, col_widths) = pack(&widths, term_width, across);
+    let rows = entries.len().div_ceil(columns);
+
+    let mut out = String::new();
+    for row in 0..rows {
+        let last_col = (0..columns).rfind(|&col| index_of(row, col, columns, rows, across) < entries.len());
+
+        #[allow(clippy::needless_range_loop)]
+        for col in 0..columns {
+            let index = index_of(row, col, columns, rows, across);
+            let Some(entry) = entries.get(index) else {
+                break;
+            };
+
+            out.push_str(entry);
+            if Some(col) != last_col {
+                let pad = col_widths[col] - widths[index];
+                for _ in 0..pad + COLUMN_SPACING {
+                    out.push(' ');
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+fn index_of(row: usize, col: usize, columns: usize, rows: usize, across: bool) -> usize {
+    if across {
+        row * columns + col
+    } else {
+        col * rows + row
+    }
+}
+
+/// Find the widest column count that fits `term_width`, returning that
+/// count along with each column's width (the max entry width in it).
+fn pack(widths: &[usize], term_width: usize, across: bool) -> (usize, Vec<usize>) {
+    let n = widths.len();
+
+    for columns in (1..=n).rev() {
+        let rows = n.div_ceil(columns);
+        let mut col_widths = vec![0usize; columns];
+
+        for (i, &w) in widths.iter().enumerate() {
+            let col = if across { i % columns } else { i / rows };
+            col_widths[col] = cmp::max(col_widths[col], w);
+        }
+
+        let total: usize = col_widths.iter().sum::<usize>() + (columns - 1) * COLUMN_SPACING;
+        if total <= term_width || columns == 1 {
+            return (columns, col_widths);
+        }
+    }
+
+    (1, vec![widths.iter().copied().max().unwrap_or(0)])
+}
@@ -0,0 +1,62 @@
+use crate::FileInfo;
+use std::os::unix::fs::PermissionsExt;
+
+/// Glyph shown for files that don't match a more specific mapping.
+const DEFAULT_ICON: char = '\u{f15b}'; // nf-fa-file
+
+/// Resolves the Nerd Font glyph for `file`: file type first (directory,
+/// symlink, executable), then filename, then extension.
+pub fn icon_for(file: &FileInfo) -> char {
+    if file.is_dir {
+        return '\u{f07b}'; // nf-fa-folder
+    }
+
+    // Same dereference rule as `get_file_color`/`classify_indicator`: under
+    // `-L` the metadata reports the target's type, so a resolved symlink
+    // should get the target's icon rather than the generic link glyph.
+    if file.is_symlink && file.metadata.file_type().is_symlink() {
+        return '\u{f0c1}'; // nf-fa-link
+    }
+
+    let mode = file.metadata.permissions().mode();
+    if mode & (libc::S_IXUSR | libc::S_IXGRP | libc::S_IXOTH) != 0 {
+        return '\u{f489}'; // nf-oct-terminal
+    }
+
+    if let Some(name) = file.path.file_name().and_then(|n| n.to_str()) {
+        if let Some(icon) = icon_for_filename(name) {
+            return icon;
+        }
+    }
+
+    if let Some(extension) = file.path.extension().and_then(|s| s.to_str()) {
+        if let Some(icon) = icon_for_extension(&extension.to_lowercase()) {
+            return icon;
+        }
+    }
+
+    DEFAULT_ICON
+}
+
+fn icon_for_filename(name: &str) -> Option<char> {
+    match name {
+        "Cargo.toml" | "Cargo.lock" => Some('\u{e7a8}'), // nf-seti-rust
+        "Makefile" => Some('\u{f489}'),                  // nf-oct-terminal
+        ".gitignore" | ".gitmodules" | ".gitattributes" => Some('\u{f1d3}'), // nf-fa-git
+        "Dockerfile" => Some('\u{f308}'),                // nf-dev-docker
+        _ => None,
+    }
+}
+
+fn icon_for_extension(extension: &str) -> Option<char> {
+    match extension {
+        "rs" => Some('\u{e7a8}'),                              // nf-seti-rust
+        "md" | "markdown" => Some('\u{f48a}'),                 // nf-dev-markdown
+        "toml" | "yaml" | "yml" | "json" => Some('\u{f0fd}'),  // nf-fa-file_text
+        "tar" | "tgz" | "gz" | "zip" | "bz2" | "xz" | "7z" | "rar" | "zst" => Some('\u{f410}'), // nf-fa-file_archive
+        "jpg" | "jpeg" | "png" | "gif" | "bmp" | "svg" | "tiff" | "webp" => Some('\u{f1c5}'), // nf-fa-file_image
+        "mp3" | "wav" | "flac" | "ogg" | "m4a" | "opus" => Some('\u{f1c7}'), // nf-fa-file_audio
+        "mp4" | "mkv" | "mov" | "avi" | "webm" => Some('\u{f1c8}'), // nf-fa-file_video
+        _ => None,
+    }
+}
@@ -0,0 +1,91 @@
+use colored::{Color, Colorize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// A resolved color, either one of the crate's built-in named colors or a
+/// raw ANSI SGR code string straight out of `LS_COLORS` (e.g. `38;5;208`).
+#[derive(Debug, Clone)]
+pub enum Style {
+    Named(Color),
+    Raw(String),
+}
+
+impl Style {
+    pub fn paint(&self, text: &str) -> String {
+        match self {
+            Style::Named(color) => text.color(*color).to_string(),
+            Style::Raw(codes) => format!("\u{1b}[{codes}m{text}\u{1b}[0m"),
+        }
+    }
+}
+
+/// Color overrides parsed from the `LS_COLORS` environment variable (the
+/// `dircolors` format), consulted before the crate's built-in extension
+/// table so the user's terminal theme is respected.
+///
+/// Type codes (`di`, `ln`, `ex`, `fi`, `or`, `so`, `pi`, `bd`, `cd`, `mi`,
+/// ...) and `*.ext` globs are both stored as raw SGR strings, so new
+/// dircolors keys can be supported without code changes anywhere else.
+#[derive(Debug, Default)]
+pub struct LsColors {
+    types: HashMap<String, String>,
+    extensions: HashMap<String, String>,
+}
+
+impl LsColors {
+    /// Style for a two-letter type code (`di`, `ln`, `so`, ...), if set.
+    pub fn type_style(&self, key: &str) -> Option<Style> {
+        self.types.get(key).cloned().map(Style::Raw)
+    }
+
+    /// Style for `filename` by longest-matching registered `*.ext` entry,
+    /// case-insensitively. `Path::extension()` only ever returns the last
+    /// dot-delimited component, so a multi-dot key like `*.tar.gz` would
+    /// never be reachable through it even though `dircolors` treats it as
+    /// more specific than `*.gz`; matching on filename suffixes lets the
+    /// longer key win regardless of how many dots it contains.
+    pub fn style_for_filename(&self, filename: &str) -> Option<Style> {
+        let lower = filename.to_lowercase();
+        self.extensions
+            .keys()
+            .filter(|ext| lower.ends_with(format!(".{ext}").as_str()))
+            .max_by_key(|ext| ext.len())
+            .and_then(|ext| self.extensions.get(ext))
+            .cloned()
+            .map(Style::Raw)
+    }
+}
+
+/// Parses `LS_COLORS` once per run and caches the result; returns an empty
+/// table (causing callers to fall back to the built-in defaults) when the
+/// variable is unset.
+pub fn parsed() -> &'static LsColors {
+    static CACHE: OnceLock<LsColors> = OnceLock::new();
+    CACHE.get_or_init(|| match std::env::var("LS_COLORS") {
+        Ok(spec) => parse(&spec),
+        Err(_) => LsColors::default(),
+    })
+}
+
+fn parse(spec: &str) -> LsColors {
+    let mut colors = LsColors::default();
+
+    for entry in spec.split(':') {
+        let Some((key, value)) = entry.split_once('=') else {
+            continue;
+        };
+        if value.is_empty() {
+            continue;
+        }
+
+        if let Some(extension) = key.strip_prefix("*.") {
+            colors
+                .extensions
+                .insert(extension.to_lowercase(), value.to_string());
+        } else {
+            colors.types.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    colors
+}
@@ -1,13 +1,23 @@
 use chrono::{DateTime, Local};
-use colored::{Color, Colorize};
+use colored::Color;
+use std::cmp;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::fs;
 use std::os::unix::fs::{MetadataExt, PermissionsExt};
 use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::{Mutex, OnceLock};
 use structopt::StructOpt;
 
+mod archive;
+mod git;
+mod grid;
+mod icons;
+mod ls_colors;
+mod natural;
+
 // Custom error type for better error handling
 #[derive(Debug)]
 enum LsError {
@@ -47,61 +57,111 @@ fn should_use_color(color_mode: &ColorMode) -> bool {
     }
 }
 
-fn get_file_color(file: &FileInfo) -> Option<Color> {
+fn get_file_color(file: &FileInfo) -> Option<ls_colors::Style> {
     let mode = file.metadata.permissions().mode();
+    let ls_colors = ls_colors::parsed();
+    let named = |color: Color| ls_colors::Style::Named(color);
 
-    // Check file type first
+    // Check file type first, honoring LS_COLORS overrides before falling
+    // back to the built-in defaults.
     if file.is_dir {
-        return Some(Color::BrightCyan);
+        return Some(ls_colors.type_style("di").unwrap_or(named(Color::BrightCyan)));
+    }
+
+    // `metadata` may already be the dereferenced target's (under `-L`), in
+    // which case it no longer reports as a symlink and we fall through to
+    // color it as whatever the target actually is.
+    if file.is_symlink && file.metadata.file_type().is_symlink() {
+        if !file.path.exists() {
+            // Broken symlink: target doesn't resolve to anything.
+            return Some(ls_colors.type_style("or").unwrap_or(named(Color::Red)));
+        }
+        return Some(ls_colors.type_style("ln").unwrap_or(named(Color::Red)));
     }
 
-    if file.is_symlink {
-        return Some(Color::Red);
+    match mode & libc::S_IFMT {
+        libc::S_IFIFO => return Some(ls_colors.type_style("pi").unwrap_or(named(Color::Yellow))),
+        libc::S_IFSOCK => return Some(ls_colors.type_style("so").unwrap_or(named(Color::Magenta))),
+        libc::S_IFBLK => return Some(ls_colors.type_style("bd").unwrap_or(named(Color::Yellow))),
+        libc::S_IFCHR => return Some(ls_colors.type_style("cd").unwrap_or(named(Color::Yellow))),
+        _ => {}
     }
 
     // Check if executable
     if mode & (libc::S_IXUSR | libc::S_IXGRP | libc::S_IXOTH) != 0 {
-        return Some(Color::BrightGreen);
+        return Some(ls_colors.type_style("ex").unwrap_or(named(Color::BrightGreen)));
     }
 
-    // Check by file extension
-    if let Some(extension) = file.path.extension().and_then(|s| s.to_str()) {
-        match extension.to_lowercase().as_str() {
-            // Archive files
-            "tar" | "tgz" | "arc" | "arj" | "taz" | "lha" | "lz4" | "lzh" | "lzma" | "tlz" |
-            "txz" | "tzo" | "t7z" | "zip" | "z" | "dz" | "gz" | "lrz" | "lz" | "lzo" |
-            "xz" | "zst" | "tzst" | "bz2" | "bz" | "tbz" | "tbz2" | "tz" | "deb" | "rpm" |
-            "jar" | "war" | "ear" | "sar" | "rar" | "alz" | "ace" | "zoo" | "cpio" | "7z" |
-            "rz" | "cab" | "wim" | "swm" | "dwm" | "esd" => Some(Color::Red),
-
-            // Image files
-            "jpg" | "jpeg" | "mjpg" | "mjpeg" | "gif" | "bmp" | "pbm" | "pgm" | "ppm" |
-            "tga" | "xbm" | "xpm" | "tif" | "tiff" | "png" | "svg" | "svgz" | "mng" |
-            "pcx" | "mov" | "mpg" | "mpeg" | "m2v" | "mkv" | "webm" | "ogm" | "mp4" |
-            "m4v" | "mp4v" | "vob" | "qt" | "nuv" | "wmv" | "asf" | "rm" | "rmvb" |
-            "flc" | "avi" | "fli" | "flv" | "gl" | "dl" | "xcf" | "xwd" | "yuv" | "cgm" |
-            "emf" | "ogv" | "ogx" => Some(Color::Magenta),
-
-            // Audio files
-            "aac" | "au" | "flac" | "m4a" | "mid" | "midi" | "mka" | "mp3" | "mpc" |
-            "ogg" | "ra" | "wav" | "oga" | "opus" | "spx" | "xspf" => Some(Color::Cyan),
-
-            _ => Some(Color::BrightYellow),
+    // Check by file extension: LS_COLORS first (matching the longest
+    // registered `*.ext` suffix, so `*.tar.gz` beats `*.gz`), then the
+    // built-in table keyed off the last dot-component only.
+    if let Some(name) = file.path.file_name().and_then(|s| s.to_str()) {
+        if let Some(style) = ls_colors.style_for_filename(name) {
+            return Some(style);
         }
+    }
+
+    if let Some(extension) = file.path.extension().and_then(|s| s.to_str()) {
+        Some(named(builtin_extension_color(extension)))
     } else {
-        None
+        ls_colors.type_style("fi")
     }
 }
 
-fn list_directory(path: &Path, opt: &Opt) -> Result<(), LsError> {
+/// The crate's built-in extension→color table, consulted once no `LS_COLORS`
+/// override matched. Shared with [`archive`] so archive members get the same
+/// fallback palette as real files.
+fn builtin_extension_color(extension: &str) -> Color {
+    match extension.to_lowercase().as_str() {
+        // Archive files
+        "tar" | "tgz" | "arc" | "arj" | "taz" | "lha" | "lz4" | "lzh" | "lzma" | "tlz" |
+        "txz" | "tzo" | "t7z" | "zip" | "z" | "dz" | "gz" | "lrz" | "lz" | "lzo" |
+        "xz" | "zst" | "tzst" | "bz2" | "bz" | "tbz" | "tbz2" | "tz" | "deb" | "rpm" |
+        "jar" | "war" | "ear" | "sar" | "rar" | "alz" | "ace" | "zoo" | "cpio" | "7z" |
+        "rz" | "cab" | "wim" | "swm" | "dwm" | "esd" => Color::Red,
+
+        // Image files
+        "jpg" | "jpeg" | "mjpg" | "mjpeg" | "gif" | "bmp" | "pbm" | "pgm" | "ppm" |
+        "tga" | "xbm" | "xpm" | "tif" | "tiff" | "png" | "svg" | "svgz" | "mng" |
+        "pcx" | "mov" | "mpg" | "mpeg" | "m2v" | "mkv" | "webm" | "ogm" | "mp4" |
+        "m4v" | "mp4v" | "vob" | "qt" | "nuv" | "wmv" | "asf" | "rm" | "rmvb" |
+        "flc" | "avi" | "fli" | "flv" | "gl" | "dl" | "xcf" | "xwd" | "yuv" | "cgm" |
+        "emf" | "ogv" | "ogx" => Color::Magenta,
+
+        // Audio files
+        "aac" | "au" | "flac" | "m4a" | "mid" | "midi" | "mka" | "mp3" | "mpc" |
+        "ogg" | "ra" | "wav" | "oga" | "opus" | "spx" | "xspf" => Color::Cyan,
+
+        _ => Color::BrightYellow,
+    }
+}
+
+/// Lists `path`, printing its entries, and returns the (non-symlink)
+/// subdirectories found so callers can descend into them for `-R`.
+fn list_directory(path: &Path, opt: &Opt) -> Result<Vec<PathBuf>, LsError> {
     let mut entries = Vec::new();
     let use_color = should_use_color(&opt.color);
     let show_counts = !opt.no_dir_counts;
 
     if path.is_dir() {
         for entry in fs::read_dir(path)? {
-            let entry = entry?;
-            let file_info = FileInfo::new(entry, show_counts)?;
+            // A single unreadable entry (e.g. a race with deletion, or a
+            // permission quirk on one file) shouldn't take down the listing
+            // for the rest of the directory, let alone the whole `-R` walk.
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    eprintln!("ls: {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            let file_info = match FileInfo::new(entry, show_counts, opt.dereference) {
+                Ok(file_info) => file_info,
+                Err(e) => {
+                    eprintln!("ls: {}: {}", path.display(), e);
+                    continue;
+                }
+            };
 
             if should_show_file(&file_info.name, opt.all) {
                 entries.push(file_info);
@@ -109,7 +169,7 @@ fn list_directory(path: &Path, opt: &Opt) -> Result<(), LsError> {
         }
     } else {
         // Single file
-        let file_info = FileInfo::from_path(path, show_counts)?;
+        let file_info = FileInfo::from_path(path, show_counts, opt.dereference)?;
         entries.push(file_info);
     }
 
@@ -126,11 +186,21 @@ fn list_directory(path: &Path, opt: &Opt) -> Result<(), LsError> {
     }
 
     // Sort both groups separately
+    let sort_key = opt.sort_key();
     let sort_func = |entries: &mut Vec<FileInfo>| {
-        if opt.sort_time {
-            entries.sort_by_key(|f| f.metadata.modified().unwrap_or(std::time::UNIX_EPOCH));
-        } else {
-            entries.sort_by(|a, b| a.name.cmp(&b.name));
+        match sort_key {
+            SortKey::Unsorted => {}
+            SortKey::Size => entries.sort_by_key(|f| cmp::Reverse(f.metadata.len())),
+            SortKey::Extension => entries.sort_by(|a, b| {
+                let ext_a = a.path.extension().and_then(|s| s.to_str()).unwrap_or("");
+                let ext_b = b.path.extension().and_then(|s| s.to_str()).unwrap_or("");
+                ext_a.cmp(ext_b).then_with(|| a.name.cmp(&b.name))
+            }),
+            SortKey::Natural => entries.sort_by(|a, b| natural::natural_cmp(&a.name, &b.name)),
+            SortKey::Time => {
+                entries.sort_by_key(|f| f.metadata.modified().unwrap_or(std::time::UNIX_EPOCH))
+            }
+            SortKey::Name => entries.sort_by(|a, b| a.name.cmp(&b.name)),
         }
 
         if opt.reverse {
@@ -141,36 +211,70 @@ fn list_directory(path: &Path, opt: &Opt) -> Result<(), LsError> {
     sort_func(&mut directories);
     sort_func(&mut files);
 
+    let git_statuses = if opt.git { git::load(path) } else { None };
+
     // Print entries with grouping
     if opt.long {
-        // Print directories first
-        for file in &directories {
-            print_long_format(file, opt.human_readable, use_color, show_counts)?;
-        }
+        let build = |file: &FileInfo| {
+            build_long_row(
+                file,
+                opt.human_readable,
+                use_color,
+                show_counts,
+                opt.icons,
+                opt.classify,
+                git_statuses.as_deref(),
+            )
+        };
+
+        let directory_rows = directories
+            .iter()
+            .map(build)
+            .collect::<Result<Vec<_>, _>>()?;
+        let file_rows = files.iter().map(build).collect::<Result<Vec<_>, _>>()?;
+
+        // Column widths are measured across the whole listing so directories
+        // and files line up with each other, not just within their own group.
+        let all_rows: Vec<&LongRow> = directory_rows.iter().chain(file_rows.iter()).collect();
+        let nlink_w = all_rows.iter().map(|r| r.nlink.len()).max().unwrap_or(0);
+        let owner_w = all_rows.iter().map(|r| r.owner.len()).max().unwrap_or(0);
+        let group_w = all_rows.iter().map(|r| r.group.len()).max().unwrap_or(0);
+        let size_w = all_rows.iter().map(|r| r.size.len()).max().unwrap_or(0);
+        let inode_w = all_rows.iter().map(|r| r.inode.len()).max().unwrap_or(0);
+        let blocks_w = all_rows.iter().map(|r| r.blocks.len()).max().unwrap_or(0);
+        let widths = LongColumnWidths {
+            nlink: nlink_w,
+            owner: owner_w,
+            group: group_w,
+            size: size_w,
+            inode: inode_w,
+            blocks: blocks_w,
+        };
+
+        print_long_rows(&directory_rows, opt.inode, opt.blocks, &widths);
 
         // Add line break between directories and files if both exist
         if !directories.is_empty() && !files.is_empty() {
             println!();
         }
 
-        // Print files
-        for file in &files {
-            print_long_format(file, opt.human_readable, use_color, show_counts)?;
-        }
+        print_long_rows(&file_rows, opt.inode, opt.blocks, &widths);
     } else {
         // Short format with grouping
         let has_dirs = !directories.is_empty();
         let has_files = !files.is_empty();
+        let term_width = grid::terminal_width();
 
         // Print spacer line
         println!();
 
         // Print directories first
         if has_dirs {
-            for file in &directories {
-                print_short_format(file, use_color, show_counts);
-            }
-            println!(); // End the directory line
+            let names: Vec<String> = directories
+                .iter()
+                .map(|file| format_filename_with_indicators(file, use_color, show_counts, opt.icons, opt.classify))
+                .collect();
+            print!("{}", grid::render(&names, term_width, opt.across, opt.oneline));
         }
 
         // Add separation line if we have both directories and files
@@ -180,15 +284,22 @@ fn list_directory(path: &Path, opt: &Opt) -> Result<(), LsError> {
 
         // Print files
         if has_files {
-            for file in &files {
-                print_short_format(file, use_color, show_counts);
-            }
-            println!(); // End the files line
+            let names: Vec<String> = files
+                .iter()
+                .map(|file| format_filename_with_indicators(file, use_color, show_counts, opt.icons, opt.classify))
+                .collect();
+            print!("{}", grid::render(&names, term_width, opt.across, opt.oneline));
         }
         println!(); // Final spacer line
     }
 
-    Ok(())
+    let subdirs = directories
+        .iter()
+        .filter(|f| !f.is_symlink)
+        .map(|f| f.path.clone())
+        .collect();
+
+    Ok(subdirs)
 }
 
 
@@ -198,12 +309,36 @@ fn colorize_filename(file: &FileInfo, use_color: bool) -> String {
     }
 
     match get_file_color(file) {
-        Some(color) => file.name.color(color).to_string(),
+        Some(style) => style.paint(&file.name),
         None => file.name.clone(),
     }
 }
-fn format_filename_with_indicators(file: &FileInfo, use_color: bool, show_counts: bool) -> String {
+
+fn colorize_icon(file: &FileInfo, use_color: bool) -> String {
+    let icon = icons::icon_for(file).to_string();
+
+    if !use_color {
+        return icon;
+    }
+
+    match get_file_color(file) {
+        Some(style) => style.paint(&icon),
+        None => icon,
+    }
+}
+fn format_filename_with_indicators(
+    file: &FileInfo,
+    use_color: bool,
+    show_counts: bool,
+    show_icons: bool,
+    classify: bool,
+) -> String {
     let colored_name = colorize_filename(file, use_color);
+    let colored_name = if show_icons {
+        format!("{} {}", colorize_icon(file, use_color), colored_name)
+    } else {
+        colored_name
+    };
 
     if file.is_dir && show_counts {
         match file.dir_count {
@@ -213,11 +348,31 @@ fn format_filename_with_indicators(file: &FileInfo, use_color: bool, show_counts
     } else if file.is_dir {
         // Only show "/" when counts are disabled
         format!("{}/", colored_name)
+    } else if classify {
+        format!("{}{}", colored_name, classify_indicator(file))
     } else {
         colored_name
     }
 }
 
+/// `-F`/`--classify` suffix for non-directory entries: `*` executable,
+/// `@` symlink, `|` FIFO, `=` socket, nothing for a plain regular file.
+fn classify_indicator(file: &FileInfo) -> &'static str {
+    // As in `get_file_color`, a dereferenced symlink under `-L` reports its
+    // target's mode, so it should classify as the target, not as `@`.
+    if file.is_symlink && file.metadata.file_type().is_symlink() {
+        return "@";
+    }
+
+    let mode = file.metadata.permissions().mode();
+    match mode & libc::S_IFMT {
+        libc::S_IFIFO => "|",
+        libc::S_IFSOCK => "=",
+        _ if mode & (libc::S_IXUSR | libc::S_IXGRP | libc::S_IXOTH) != 0 => "*",
+        _ => "",
+    }
+}
+
 
 impl Error for LsError {}
 
@@ -227,6 +382,36 @@ impl From<std::io::Error> for LsError {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+enum SortMode {
+    Name,
+    Natural,
+}
+
+impl std::str::FromStr for SortMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "name" => Ok(SortMode::Name),
+            "natural" | "version" => Ok(SortMode::Natural),
+            _ => Err(format!("Invalid sort mode: {}", s)),
+        }
+    }
+}
+
+/// The comparison `list_directory`'s sort closure picks between, resolved
+/// once from `Opt`'s individual sort flags by [`Opt::sort_key`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortKey {
+    Name,
+    Natural,
+    Size,
+    Extension,
+    Time,
+    Unsorted,
+}
+
 #[derive(Debug, Clone)]
 enum ColorMode {
     Never,
@@ -270,6 +455,22 @@ struct Opt {
     #[structopt(short = "t", long = "time")]
     sort_time: bool,
 
+    /// Sort by file size, largest first
+    #[structopt(short = "S", long = "size")]
+    sort_size: bool,
+
+    /// Sort by file extension, then name
+    #[structopt(short = "X", long = "extension")]
+    sort_extension: bool,
+
+    /// Sort order: "name" (default) or "natural" (file9 before file10)
+    #[structopt(long = "sort")]
+    sort: Option<SortMode>,
+
+    /// List entries in raw directory order; don't sort at all
+    #[structopt(short = "U", long = "unsorted")]
+    unsorted: bool,
+
     /// Control color output
     #[structopt(long = "color", default_value = "auto")]
     color: ColorMode,
@@ -278,11 +479,75 @@ struct Opt {
     #[structopt(long = "no-dir-counts", short = "C")]
     no_dir_counts: bool,
 
+    /// Fill the short-format grid row-major (left to right) instead of
+    /// column-major (top to bottom)
+    #[structopt(short = "x", long = "across")]
+    across: bool,
+
+    /// List one entry per line instead of a multi-column grid
+    #[structopt(short = "1", long = "oneline")]
+    oneline: bool,
+
+    /// Recursively list subdirectories
+    #[structopt(short = "R", long = "recursive")]
+    recursive: bool,
+
+    /// Show a git status column in long format
+    #[structopt(long = "git")]
+    git: bool,
+
+    /// Prepend a Nerd Font icon to each entry
+    #[structopt(long = "icons")]
+    icons: bool,
+
+    /// Peek inside tar archives (.tar, .tar.gz, .tgz) and list their members
+    /// as if they were a directory
+    #[structopt(short = "A", long = "archive", alias = "inspect", alias = "tree-archives")]
+    archive: bool,
+
+    /// Append type indicators to entries: `*` executable, `@` symlink,
+    /// `|` FIFO, `=` socket
+    #[structopt(short = "F", long = "classify")]
+    classify: bool,
+
+    /// Show each entry's inode number in long format
+    #[structopt(short = "i", long = "inode")]
+    inode: bool,
+
+    /// Show each entry's allocated block count in long format
+    #[structopt(short = "s", long = "blocks")]
+    blocks: bool,
+
+    /// Follow symlinks: stat the target instead of the link itself, so its
+    /// type, size, and color reflect the destination
+    #[structopt(short = "L", long = "dereference")]
+    dereference: bool,
+
     /// Paths to list
     #[structopt(parse(from_os_str))]
     paths: Vec<PathBuf>,
 }
 
+impl Opt {
+    /// Resolves the individual `--sort`/`-S`/`-X`/`-t`/`-U` flags to a single
+    /// [`SortKey`], in the order the old boolean chain checked them.
+    fn sort_key(&self) -> SortKey {
+        if self.unsorted {
+            SortKey::Unsorted
+        } else if self.sort_size {
+            SortKey::Size
+        } else if self.sort_extension {
+            SortKey::Extension
+        } else if self.sort == Some(SortMode::Natural) {
+            SortKey::Natural
+        } else if self.sort_time {
+            SortKey::Time
+        } else {
+            SortKey::Name
+        }
+    }
+}
+
 #[derive(Debug)]
 struct FileInfo {
     name: String,
@@ -294,14 +559,15 @@ struct FileInfo {
 }
 
 impl FileInfo {
-    fn new(entry: fs::DirEntry, count_dirs: bool) -> Result<Self, LsError> {
-        let metadata = entry.metadata()?;
+    fn new(entry: fs::DirEntry, count_dirs: bool, dereference: bool) -> Result<Self, LsError> {
+        let lstat = entry.metadata()?;
         let path = entry.path();
         let name = entry
             .file_name()
             .into_string()
             .map_err(|_| LsError::InvalidFileName(format!("{:?}", entry.file_name())))?;
 
+        let metadata = resolve_metadata(&path, lstat, dereference);
         let is_dir = path.is_dir();
         let is_symlink = path.is_symlink();
         let dir_count = if is_dir && count_dirs {
@@ -320,14 +586,15 @@ impl FileInfo {
         })
     }
 
-    fn from_path(path: &Path, count_dirs: bool) -> Result<Self, LsError> {
-        let metadata = path.metadata()?;
+    fn from_path(path: &Path, count_dirs: bool, dereference: bool) -> Result<Self, LsError> {
+        let lstat = fs::symlink_metadata(path)?;
         let name = path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("")
             .to_string();
 
+        let metadata = resolve_metadata(path, lstat, dereference);
         let is_dir = path.is_dir();
         let is_symlink = path.is_symlink();
         let dir_count = if is_dir && count_dirs {
@@ -347,6 +614,18 @@ impl FileInfo {
     }
 }
 
+/// Picks the metadata a [`FileInfo`] should report: the link's own (`lstat`)
+/// metadata normally, or the target's metadata when `dereference` is set and
+/// the target actually resolves. A broken link under `-L` falls back to
+/// `lstat`, which keeps it identifiable as a symlink rather than erroring out.
+fn resolve_metadata(path: &Path, lstat: fs::Metadata, dereference: bool) -> fs::Metadata {
+    if dereference && lstat.file_type().is_symlink() {
+        fs::metadata(path).unwrap_or(lstat)
+    } else {
+        lstat
+    }
+}
+
 fn format_permissions(mode: u32) -> String {
     let file_type = match mode & libc::S_IFMT {
         libc::S_IFDIR => 'd',
@@ -393,31 +672,133 @@ fn format_size(size: u64, human_readable: bool) -> String {
     }
 }
 
-fn print_long_format(file: &FileInfo, human_readable: bool, use_color: bool, show_counts: bool) -> Result<(), LsError> {
+/// A single pre-formatted long-format row. Every field is rendered to its
+/// final text up front so that each column's width can be measured across
+/// the whole listing before [`print_long_rows`] prints anything.
+struct LongRow {
+    permissions: String,
+    nlink: String,
+    owner: String,
+    group: String,
+    size: String,
+    inode: String,
+    blocks: String,
+    time: String,
+    git_column: Option<String>,
+    name: String,
+}
+
+fn build_long_row(
+    file: &FileInfo,
+    human_readable: bool,
+    use_color: bool,
+    show_counts: bool,
+    show_icons: bool,
+    classify: bool,
+    git_statuses: Option<&HashMap<PathBuf, git::GitStatus>>,
+) -> Result<LongRow, LsError> {
     let mode = file.metadata.permissions().mode();
-    let nlink = file.metadata.nlink();
-    let size = file.metadata.len();
     let modified: DateTime<Local> = DateTime::from(file.metadata.modified()?);
+    let mut name = format_filename_with_indicators(file, use_color, show_counts, show_icons, classify);
+
+    // Once dereferenced under `-L` (metadata no longer reports as a
+    // symlink), the name should read like the target's own entry, with no
+    // arrow — matching real `ls -lL`.
+    if file.is_symlink && file.metadata.file_type().is_symlink() {
+        if let Ok(target) = fs::read_link(&file.path) {
+            name = format!("{} -> {}", name, target.display());
+        }
+    }
 
-    let formatted_size = format_size(size, human_readable);
-    let time_str = modified.format("%b %d %H:%M").to_string();
-    let formatted_name = format_filename_with_indicators(file, use_color, show_counts);
+    Ok(LongRow {
+        permissions: format_permissions(mode),
+        nlink: file.metadata.nlink().to_string(),
+        owner: resolve_user_name(file.metadata.uid()),
+        group: resolve_group_name(file.metadata.gid()),
+        size: format_size(file.metadata.len(), human_readable),
+        inode: file.metadata.ino().to_string(),
+        blocks: file.metadata.blocks().to_string(),
+        time: modified.format("%b %d %H:%M").to_string(),
+        git_column: git_statuses.map(|statuses| git::column(statuses, &file.path, use_color)),
+        name,
+    })
+}
 
-    println!(
-        "{} {:>3} {:>8} {} {}",
-        format_permissions(mode),
-        nlink,
-        formatted_size,
-        time_str,
-        formatted_name
-    );
+/// Resolves `uid` to a username via the `users` crate, caching lookups for
+/// the life of the process; falls back to the numeric id when resolution
+/// fails (e.g. the user was deleted, or we're sandboxed without `/etc/passwd`
+/// access).
+fn resolve_user_name(uid: u32) -> String {
+    static CACHE: OnceLock<Mutex<HashMap<u32, String>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cache = cache.lock().unwrap();
+    cache
+        .entry(uid)
+        .or_insert_with(|| {
+            users::get_user_by_uid(uid)
+                .map(|u| u.name().to_string_lossy().into_owned())
+                .unwrap_or_else(|| uid.to_string())
+        })
+        .clone()
+}
 
-    Ok(())
+/// Same as [`resolve_user_name`] but for group ids.
+fn resolve_group_name(gid: u32) -> String {
+    static CACHE: OnceLock<Mutex<HashMap<u32, String>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cache = cache.lock().unwrap();
+    cache
+        .entry(gid)
+        .or_insert_with(|| {
+            users::get_group_by_gid(gid)
+                .map(|g| g.name().to_string_lossy().into_owned())
+                .unwrap_or_else(|| gid.to_string())
+        })
+        .clone()
 }
 
-fn print_short_format(file: &FileInfo, use_color: bool, show_counts: bool) {
-    let formatted_name = format_filename_with_indicators(file, use_color, show_counts);
-    print!("{}  ", formatted_name);
+/// Column widths measured across an entire listing (directories and files
+/// together) so every row lines up regardless of which group it's in.
+struct LongColumnWidths {
+    nlink: usize,
+    owner: usize,
+    group: usize,
+    size: usize,
+    inode: usize,
+    blocks: usize,
+}
+
+/// Prints `rows` using pre-measured `widths`, right-aligning numeric columns
+/// and left-aligning owner/group, like a real aligned `ls -l` table.
+fn print_long_rows(rows: &[LongRow], show_inode: bool, show_blocks: bool, widths: &LongColumnWidths) {
+    for row in rows {
+        if show_inode {
+            print!("{:>width$} ", row.inode, width = widths.inode);
+        }
+        if show_blocks {
+            print!("{:>width$} ", row.blocks, width = widths.blocks);
+        }
+
+        print!(
+            "{} {:>nlink_w$} {:<owner_w$} {:<group_w$} {:>size_w$} {}",
+            row.permissions,
+            row.nlink,
+            row.owner,
+            row.group,
+            row.size,
+            row.time,
+            nlink_w = widths.nlink,
+            owner_w = widths.owner,
+            group_w = widths.group,
+            size_w = widths.size,
+        );
+
+        if let Some(git_column) = &row.git_column {
+            print!(" {}", git_column);
+        }
+
+        println!(" {}", row.name);
+    }
 }
 
 fn should_show_file(name: &str, show_all: bool) -> bool {
@@ -432,21 +813,85 @@ fn run(opt: &Opt) -> Result<(), LsError> {
         opt.paths.clone()
     };
 
-    for (i, path) in paths.iter().enumerate() {
-        if paths.len() > 1 {
-            if i > 0 {
-                println!();
-            }
-            println!("{}:", path.display());
+    let show_header = paths.len() > 1 || opt.recursive;
+    let mut first = true;
+
+    for path in &paths {
+        list_recursive(path, opt, show_header, &mut first);
+    }
+
+    Ok(())
+}
+
+/// Lists `path`, then (when `opt.recursive`) walks into each subdirectory it
+/// found, printing a `path:` header before every level once `show_header` is
+/// set. `first` suppresses the blank line before the very first header.
+///
+/// Under `--archive`, a `path` that's itself a tar file is streamed and
+/// rendered as if it were a directory (see [`list_archive_recursive`])
+/// instead of being listed as a single regular file.
+fn list_recursive(path: &Path, opt: &Opt, show_header: bool, first: &mut bool) {
+    if opt.archive && path.is_file() && archive::is_archive(path) {
+        if let Some(tree) = archive::scan(path) {
+            list_archive_recursive(&tree, "", path, opt, first);
+            return;
+        }
+    }
+
+    if show_header {
+        if !*first {
+            println!();
         }
+        println!("{}:", path.display());
+    }
+    *first = false;
 
-        if let Err(e) = list_directory(path, opt) {
+    let subdirs = match list_directory(path, opt) {
+        Ok(subdirs) => subdirs,
+        Err(e) => {
             eprintln!("ls: {}: {}", path.display(), e);
-            continue;
+            return;
+        }
+    };
+
+    if opt.recursive {
+        for dir in subdirs {
+            list_recursive(&dir, opt, true, first);
         }
     }
+}
 
-    Ok(())
+/// Lists the virtual directory `dir` out of `tree` (`""` for the archive
+/// root), then — under `-R` — recurses into the subdirectories `archive`
+/// found inside it, printing headers in terms of `archive_path` the same way
+/// a real nested directory would. Always shows a header, even when the
+/// archive is the only path listed and `-R` is not set.
+fn list_archive_recursive(
+    tree: &archive::Tree,
+    dir: &str,
+    archive_path: &Path,
+    opt: &Opt,
+    first: &mut bool,
+) {
+    let display = if dir.is_empty() {
+        archive_path.to_path_buf()
+    } else {
+        archive_path.join(dir)
+    };
+
+    if !*first {
+        println!();
+    }
+    println!("{}:", display.display());
+    *first = false;
+
+    let subdirs = archive::render(tree, dir, opt);
+
+    if opt.recursive {
+        for subdir in subdirs {
+            list_archive_recursive(tree, &subdir, archive_path, opt, first);
+        }
+    }
 }
 
 fn main() {
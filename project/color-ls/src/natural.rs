@@ -0,0 +1,68 @@
+use std::cmp::Ordering;
+
+/// Natural/version-aware string comparison (`file9` sorts before `file10`),
+/// matching exa's natord-based ordering.
+///
+/// Walks both strings run by run: when both current runs are digits they're
+/// compared as integers (ignoring leading zeros, with the longer original
+/// run winning ties), otherwise the two strings are compared byte by byte.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a = a.as_bytes();
+    let mut b = b.as_bytes();
+
+    loop {
+        match (a.first(), b.first()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(&ac), Some(&bc)) => {
+                if ac.is_ascii_digit() && bc.is_ascii_digit() {
+                    let a_len = a.iter().take_while(|c| c.is_ascii_digit()).count();
+                    let b_len = b.iter().take_while(|c| c.is_ascii_digit()).count();
+                    let (a_run, a_rest) = a.split_at(a_len);
+                    let (b_run, b_rest) = b.split_at(b_len);
+
+                    match compare_numeric(a_run, b_run) {
+                        Ordering::Equal => {
+                            a = a_rest;
+                            b = b_rest;
+                        }
+                        other => return other,
+                    }
+                } else if ac != bc {
+                    return ac.cmp(&bc);
+                } else {
+                    a = &a[1..];
+                    b = &b[1..];
+                }
+            }
+        }
+    }
+}
+
+/// Compares two runs of ASCII digits by value, ignoring leading zeros; if
+/// the values are equal, the longer (untrimmed) run sorts after the shorter.
+fn compare_numeric(a_run: &[u8], b_run: &[u8]) -> Ordering {
+    let a_trimmed = trim_leading_zeros(a_run);
+    let b_trimmed = trim_leading_zeros(b_run);
+
+    let by_value = a_trimmed
+        .len()
+        .cmp(&b_trimmed.len())
+        .then_with(|| a_trimmed.cmp(b_trimmed));
+
+    if by_value != Ordering::Equal {
+        return by_value;
+    }
+
+    a_run.len().cmp(&b_run.len())
+}
+
+fn trim_leading_zeros(run: &[u8]) -> &[u8] {
+    let zeros = run.iter().take_while(|&&c| c == b'0').count();
+    if zeros == run.len() {
+        &run[run.len() - 1..]
+    } else {
+        &run[zeros..]
+    }
+}